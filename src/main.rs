@@ -1,6 +1,7 @@
 use chrono::{prelude::*, serde::ts_seconds, Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use iif::iif;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 #[cfg(feature = "binary")]
 use std::path::PathBuf;
@@ -24,7 +25,7 @@ enum Command {
 
         /// the time at which the event happend.
         /// format: "HH:MM:SS" or "YY-mm-dd HH:MM:SS" [defaults to current time]
-        #[structopt(short, long)]
+        #[structopt(short, long, allow_hyphen_values = true)]
         at: Option<String>,
     },
 
@@ -35,7 +36,7 @@ enum Command {
 
         /// the time at which the event happend.
         /// format: "HH:MM:SS" or "YY-mm-dd HH:MM:SS" [defaults to current time]
-        #[structopt(short, long)]
+        #[structopt(short, long, allow_hyphen_values = true)]
         at: Option<String>,
     },
 
@@ -51,11 +52,29 @@ enum Command {
     /// show work time for given timespan
     Show {
         /// the start time [defaults to current day 00:00:00]
-        #[structopt(short, long)]
+        #[structopt(short, long, allow_hyphen_values = true)]
         from: Option<String>,
 
         /// the stop time [defaults to start day 23:59:59]
-        #[structopt(short, long)]
+        #[structopt(short, long, allow_hyphen_values = true)]
+        to: Option<String>,
+
+        /// include seconds in time calculation
+        #[structopt(short)]
+        include_seconds: bool,
+
+        /// filter entries. possible filter values: "week" or part of the description
+        filter: Option<String>,
+    },
+
+    /// show a per-description breakdown of work time for given timespan
+    Report {
+        /// the start time [defaults to current day 00:00:00]
+        #[structopt(short, long, allow_hyphen_values = true)]
+        from: Option<String>,
+
+        /// the stop time [defaults to start day 23:59:59]
+        #[structopt(short, long, allow_hyphen_values = true)]
         to: Option<String>,
 
         /// include seconds in time calculation
@@ -72,6 +91,66 @@ enum Command {
         /// where to write the json file
         path: PathBuf,
     },
+
+    #[cfg(feature = "binary")]
+    /// export tracked time as a visual, weekly HTML calendar
+    Calendar {
+        /// the start date [defaults to the current week's monday]
+        #[structopt(short, long, allow_hyphen_values = true)]
+        from: Option<String>,
+
+        /// the stop date [defaults to start date + 6 days]
+        #[structopt(short, long, allow_hyphen_values = true)]
+        to: Option<String>,
+
+        /// replace descriptions with a generic "busy" label
+        #[structopt(short, long)]
+        private: bool,
+
+        /// where to write the html file
+        path: PathBuf,
+    },
+
+    #[cfg(feature = "binary")]
+    /// export the tracking data as csv, for spreadsheet interop
+    ExportCsv {
+        /// where to write the csv file
+        path: PathBuf,
+    },
+
+    #[cfg(feature = "binary")]
+    /// import tracking data from a csv file, replacing all current entries
+    ImportCsv {
+        /// the csv file to read
+        path: PathBuf,
+    },
+
+    #[cfg(feature = "binary")]
+    /// export the file as emacs org-mode CLOCK entries
+    ExportOrg {
+        /// where to write the org file
+        path: PathBuf,
+    },
+
+    #[cfg(feature = "binary")]
+    /// import tracking data from emacs org-mode CLOCK entries, replacing all current entries
+    ImportOrg {
+        /// the org file to read
+        path: PathBuf,
+    },
+
+    /// configure the recurring expected-work schedule used for the overtime/undertime balance
+    Schedule {
+        /// an iCalendar-style recurrence rule, e.g. "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR"
+        rrule: String,
+
+        /// the expected work duration on a matching day. format: "HH:MM:SS"
+        daily_target: String,
+
+        /// the first day the schedule applies from [defaults to today]
+        #[structopt(short, long, allow_hyphen_values = true)]
+        from: Option<String>,
+    },
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -124,40 +203,403 @@ impl TrackingEvent {
     }
 }
 
+/// a recurring expected-work schedule, expanded from an iCalendar-style RRULE
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Schedule {
+    freq: Freq,
+    interval: u32,
+    by_day: Vec<Weekday>,
+    #[serde(with = "duration_seconds")]
+    daily_target: Duration,
+    dtstart: NaiveDate,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+enum Freq {
+    Weekly,
+}
+
+/// (de)serialize a [`Duration`] as a plain number of seconds, since chrono has no built-in impl
+mod duration_seconds {
+    use chrono::Duration;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        duration.num_seconds().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        i64::deserialize(deserializer).map(Duration::seconds)
+    }
+}
+
+impl Schedule {
+    /// the monday of the week `date` falls into
+    fn monday_of(date: NaiveDate) -> NaiveDate {
+        date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+    }
+
+    /// sum up the expected work duration for every day in `[from, to]` that matches this
+    /// schedule's `BYDAY` set and `INTERVAL`, counted in weeks since `dtstart`; days before
+    /// `dtstart` never count
+    fn expand(&self, from: NaiveDate, to: NaiveDate) -> Duration {
+        let start_monday = Self::monday_of(self.dtstart);
+        let mut total = Duration::zero();
+        let mut date = from;
+        while date <= to {
+            if date >= self.dtstart && self.by_day.contains(&date.weekday()) {
+                let weeks = (Self::monday_of(date) - start_monday).num_days() / 7;
+                if weeks >= 0 && weeks % i64::from(self.interval) == 0 {
+                    total = total
+                        .checked_add(&self.daily_target)
+                        .expect("couldn't add up durations");
+                }
+            }
+            date = date.succ();
+        }
+        total
+    }
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday, String> {
+    match s {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        other => Err(format!("invalid BYDAY value `{}`", other)),
+    }
+}
+
+/// parse an iCalendar-style RRULE (e.g. `FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR`) into a [`Schedule`]
+fn parse_rrule(rrule: &str, dtstart: NaiveDate, daily_target: Duration) -> Result<Schedule, String> {
+    let mut freq = None;
+    let mut interval = 1;
+    let mut by_day = Vec::new();
+
+    for part in rrule.split(';') {
+        let mut kv = part.splitn(2, '=');
+        let key = kv.next().unwrap_or_default();
+        let value = kv.next().unwrap_or_default();
+        match key {
+            "FREQ" => {
+                freq = Some(match value {
+                    "WEEKLY" => Freq::Weekly,
+                    other => return Err(format!("unsupported FREQ `{}`", other)),
+                });
+            }
+            "INTERVAL" => {
+                interval = value
+                    .parse()
+                    .map_err(|_| format!("invalid INTERVAL `{}`", value))?;
+                if interval == 0 {
+                    return Err("INTERVAL must not be 0".to_owned());
+                }
+            }
+            "BYDAY" => {
+                for day in value.split(',') {
+                    by_day.push(parse_weekday(day)?);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Schedule {
+        freq: freq.ok_or_else(|| "RRULE is missing FREQ".to_owned())?,
+        interval,
+        by_day,
+        daily_target,
+        dtstart,
+    })
+}
+
+fn set_schedule(data: &mut Data, rrule: String, daily_target: String, from: Option<String>) {
+    let dtstart = from
+        .map(|s| or_exit(parse_date_or_date_time(&s)).date())
+        .unwrap_or_else(|| Local::today().naive_local());
+    let target_time = NaiveTime::parse_from_str(&daily_target, "%H:%M:%S")
+        .map_err(|_| format!("daily target `{}` must be in the HH:MM:SS format", daily_target));
+    let target_time = or_exit(target_time);
+    let daily_target = target_time - NaiveTime::from_hms(0, 0, 0);
+
+    data.schedule = Some(or_exit(parse_rrule(&rrule, dtstart, daily_target)));
+}
+
 #[derive(Debug, Clone, Copy)]
 enum DateOrDateTime {
     Date(NaiveDate),
     DateTime(NaiveDateTime),
 }
 
+impl DateOrDateTime {
+    fn date(self) -> NaiveDate {
+        match self {
+            Self::Date(date) => date,
+            Self::DateTime(date_time) => date_time.date(),
+        }
+    }
+}
+
+/// a Start/Stop pair of events, or a still-running Start with no matching Stop yet
+enum Interval<'a> {
+    Closed(&'a TrackingEvent, &'a TrackingEvent),
+    Open(&'a TrackingEvent),
+}
+
+/// turn a stream of Start/Stop events into intervals, dropping a leading orphan Stop
+/// and keeping a trailing unmatched Start as an still-running [`Interval::Open`]
+fn pair_events<'a>(data: impl Iterator<Item = &'a TrackingEvent>) -> Vec<Interval<'a>> {
+    let mut data = data.skip_while(|entry| TrackingEvent::is_stop(entry));
+    let mut intervals = Vec::new();
+    loop {
+        let start = data.next();
+        let stop = data.next();
+        match (start, stop) {
+            (Some(start), Some(stop)) => intervals.push(Interval::Closed(start, stop)),
+            (Some(start), None) => {
+                intervals.push(Interval::Open(start));
+                break;
+            }
+            (_, _) => break,
+        }
+    }
+    intervals
+}
+
+/// everything persisted to the data file: the tracked events plus the optional
+/// expected-work [`Schedule`] used for the overtime/undertime balance
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct Data {
+    events: Vec<TrackingEvent>,
+    schedule: Option<Schedule>,
+}
+
+/// upgrades the contents of a pre-[`Data`] data file, where the file held nothing
+/// but the bare list of events, to the current format
+fn migrate_events(events: Vec<TrackingEvent>) -> Data {
+    Data {
+        events,
+        schedule: None,
+    }
+}
+
 #[cfg(feature = "binary")]
-fn read_data<P: AsRef<Path>>(path: P) -> Vec<TrackingEvent> {
+fn read_data<P: AsRef<Path>>(path: P) -> Data {
     let data = std::fs::read(&path).unwrap_or_default();
-    bincode::deserialize(&data).unwrap_or_default()
+    bincode::deserialize(&data)
+        .or_else(|_| bincode::deserialize(&data).map(migrate_events))
+        .unwrap_or_default()
 }
 
 #[cfg(not(feature = "binary"))]
-fn read_data<P: AsRef<Path>>(path: P) -> Vec<TrackingEvent> {
+fn read_data<P: AsRef<Path>>(path: P) -> Data {
     let data = std::fs::read_to_string(&path).unwrap_or_default();
-    serde_json::from_str(&data).unwrap_or_default()
+    serde_json::from_str(&data)
+        .or_else(|_| serde_json::from_str(&data).map(migrate_events))
+        .unwrap_or_default()
 }
 
 #[cfg(feature = "binary")]
-fn write_data<P: AsRef<Path>>(path: P, data: &[TrackingEvent]) {
+fn write_data<P: AsRef<Path>>(path: P, data: &Data) {
     let data = bincode::serialize(data).expect("could not serialize data");
     std::fs::write(path, data).expect("could not write data file");
 }
 
-fn write_data_json<P: AsRef<Path>>(path: P, data: &[TrackingEvent]) {
+fn write_data_json<P: AsRef<Path>>(path: P, data: &Data) {
     let data = serde_json::to_string(data).expect("could not serialize data");
     std::fs::write(path, data).expect("could not write data file");
 }
 
 #[cfg(not(feature = "binary"))]
-fn write_data<P: AsRef<Path>>(path: P, data: &[TrackingEvent]) {
+fn write_data<P: AsRef<Path>>(path: P, data: &Data) {
     write_data_json(path, data);
 }
 
+/// flat, spreadsheet-friendly representation of a single [`TrackingEvent`]
+#[cfg(feature = "binary")]
+#[derive(Debug, Serialize, Deserialize)]
+struct CsvRow {
+    kind: String,
+    description: Option<String>,
+    timestamp: String,
+}
+
+#[cfg(feature = "binary")]
+impl CsvRow {
+    fn from_event(event: &TrackingEvent) -> Self {
+        let (kind, data) = match event {
+            TrackingEvent::Start(data) => ("start", data),
+            TrackingEvent::Stop(data) => ("stop", data),
+        };
+        Self {
+            kind: kind.to_owned(),
+            description: data.description.clone(),
+            timestamp: data.time.to_rfc3339(),
+        }
+    }
+
+    fn into_event(self) -> Result<TrackingEvent, String> {
+        let time = DateTime::parse_from_rfc3339(&self.timestamp)
+            .map_err(|e| format!("invalid timestamp `{}`: {}", self.timestamp, e))?
+            .with_timezone(&Utc);
+        let data = TrackingData {
+            description: self.description,
+            time,
+        };
+        match self.kind.as_str() {
+            "start" => Ok(TrackingEvent::Start(data)),
+            "stop" => Ok(TrackingEvent::Stop(data)),
+            kind => Err(format!("unknown event kind `{}`", kind)),
+        }
+    }
+}
+
+#[cfg(feature = "binary")]
+fn export_csv<P: AsRef<Path>>(path: P, data: &[TrackingEvent]) {
+    let mut writer = csv::Writer::from_path(path).expect("could not create csv file");
+    for event in data {
+        writer
+            .serialize(CsvRow::from_event(event))
+            .expect("could not write csv row");
+    }
+    writer.flush().expect("could not flush csv file");
+}
+
+#[cfg(feature = "binary")]
+fn import_csv<P: AsRef<Path>>(path: P) -> Vec<TrackingEvent> {
+    let mut reader = csv::Reader::from_path(path).expect("could not read csv file");
+    let mut data = Vec::new();
+    for (line, result) in reader.deserialize().enumerate() {
+        let row: CsvRow = match result {
+            Ok(row) => row,
+            Err(err) => {
+                eprintln!("skipping csv row {}: {}", line + 1, err);
+                continue;
+            }
+        };
+        let event = match row.into_event() {
+            Ok(event) => event,
+            Err(err) => {
+                eprintln!("skipping csv row {}: {}", line + 1, err);
+                continue;
+            }
+        };
+
+        let expects_start = data.last().map_or(true, TrackingEvent::is_stop);
+        if expects_start == event.is_start() {
+            data.push(event);
+        } else {
+            eprintln!(
+                "skipping csv row {}: expected a {} event but found a {}",
+                line + 1,
+                iif!(expects_start, "start", "stop"),
+                iif!(event.is_start(), "start", "stop"),
+            );
+        }
+    }
+    data
+}
+
+/// format every closed/open interval as an org-mode `CLOCK:` line, preceded by a `*` headline
+/// (or a `# (no description)` line for intervals without a description) whenever the
+/// description changes from the previous interval
+#[cfg(feature = "binary")]
+fn export_org<P: AsRef<Path>>(path: P, data: &[TrackingEvent]) {
+    let mut output = String::new();
+    let mut last_description: Option<Option<String>> = None;
+
+    for interval in pair_events(data.iter()) {
+        let (start, stop, description) = match interval {
+            Interval::Closed(start, stop) => (start, stop.time(true), start.description()),
+            Interval::Open(start) => (start, Utc::now(), start.description()),
+        };
+
+        if last_description.as_ref() != Some(&description) {
+            match &description {
+                Some(text) => output.push_str(&format!("* {}\n", text)),
+                // a `#` comment line, rather than a `*` headline, so this can never collide
+                // with a real (and possibly identically-worded) description on import
+                None => output.push_str("# (no description)\n"),
+            }
+            last_description = Some(description);
+        }
+
+        let start_local = start.time(true).with_timezone(&Local);
+        let stop_local = stop.with_timezone(&Local);
+        let minutes = stop_local.signed_duration_since(start_local).num_minutes();
+
+        output.push_str(&format!(
+            "  CLOCK: [{}]--[{}] => {}:{:02}\n",
+            start_local.format("%Y-%m-%d %a %H:%M"),
+            stop_local.format("%Y-%m-%d %a %H:%M"),
+            minutes / 60,
+            minutes % 60,
+        ));
+    }
+
+    std::fs::write(path, output).expect("could not write org file");
+}
+
+/// parse the two bracketed timestamps out of an org-mode `CLOCK:` line's remainder,
+/// e.g. `[2019-08-04 Sun 14:12]--[2019-08-04 Sun 15:30] => 1:18`
+#[cfg(feature = "binary")]
+fn parse_org_clock(s: &str) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
+    let s = s.trim_start_matches('[');
+    let mut parts = s.splitn(2, "]--[");
+    let start = parts.next().ok_or_else(|| "missing start timestamp".to_owned())?;
+    let rest = parts.next().ok_or_else(|| "missing stop timestamp".to_owned())?;
+    let stop = rest
+        .split(']')
+        .next()
+        .ok_or_else(|| "missing stop timestamp".to_owned())?;
+
+    let parse = |s: &str| -> Result<DateTime<Utc>, String> {
+        let naive = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %a %H:%M")
+            .map_err(|e| format!("invalid org timestamp `{}`: {}", s, e))?;
+        Ok(TimeZone::from_local_datetime(&Local, &naive)
+            .unwrap()
+            .with_timezone(&Utc))
+    };
+
+    Ok((parse(start)?, parse(stop)?))
+}
+
+#[cfg(feature = "binary")]
+fn import_org<P: AsRef<Path>>(path: P) -> Vec<TrackingEvent> {
+    let content = std::fs::read_to_string(path).expect("could not read org file");
+    let mut data = Vec::new();
+    let mut headline: Option<String> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed == "# (no description)" {
+            headline = None;
+            continue;
+        }
+        if let Some(text) = trimmed.strip_prefix('*') {
+            headline = Some(text.trim_start_matches('*').trim().to_owned());
+            continue;
+        }
+        if let Some(clock) = trimmed.strip_prefix("CLOCK:") {
+            let (start, stop) =
+                parse_org_clock(clock.trim()).expect("could not parse CLOCK line");
+            data.push(TrackingEvent::Start(TrackingData {
+                description: headline.clone(),
+                time: start,
+            }));
+            data.push(TrackingEvent::Stop(TrackingData {
+                description: headline.clone(),
+                time: stop,
+            }));
+        }
+    }
+    data
+}
+
 fn start_tracking(data: &mut Vec<TrackingEvent>, description: Option<String>, at: Option<String>) {
     let should_add = match data.last() {
         None => true,
@@ -166,7 +608,10 @@ fn start_tracking(data: &mut Vec<TrackingEvent>, description: Option<String>, at
     if should_add {
         data.push(TrackingEvent::Start(TrackingData {
             description,
-            time: at.map_or_else(|| Local::now().into(), |at| parse_date_time(&at)),
+            time: at.map_or_else(
+                || Local::now().into(),
+                |at| or_exit(parse_date_time(&at)),
+            ),
         }));
     }
 }
@@ -179,7 +624,10 @@ fn stop_tracking(data: &mut Vec<TrackingEvent>, description: Option<String>, at:
     if should_add {
         data.push(TrackingEvent::Stop(TrackingData {
             description,
-            time: at.map_or_else(|| Local::now().into(), |at| parse_date_time(&at)),
+            time: at.map_or_else(
+                || Local::now().into(),
+                |at| or_exit(parse_date_time(&at)),
+            ),
         }))
     }
 }
@@ -209,14 +657,15 @@ fn split_duration(duration: Duration) -> (i64, i64, i64) {
     (hours, minutes, seconds)
 }
 
-fn show(
-    data: &[TrackingEvent],
+/// turn the raw `--from`/`--to`/`filter` command line options into a resolved filter
+/// string plus the concrete date range it refers to, expanding the special "week" filter
+/// into the current week's monday/sunday
+fn resolve_range(
     from: Option<String>,
     to: Option<String>,
     filter: Option<String>,
-    include_seconds: bool,
-) -> Option<()> {
-    let (filter, from, to) = match filter {
+) -> Option<(Option<String>, Option<DateOrDateTime>, Option<DateOrDateTime>)> {
+    match filter {
         Some(from) if from == "week" => {
             let now = Local::today();
             let weekday = now.weekday();
@@ -224,27 +673,37 @@ fn show(
             let (monday_offset, sunday_offset) = (offset, 6 - offset);
             let from = DateOrDateTime::Date(now.with_day(now.day() - monday_offset)?.naive_local());
             let to = DateOrDateTime::Date(now.with_day(now.day() + sunday_offset)?.naive_local());
-            (None, Some(from), Some(to))
+            Some((None, Some(from), Some(to)))
         }
         f => {
             let from = match &from {
-                Some(s) => Some(parse_date_or_date_time(&s)),
+                Some(s) => Some(or_exit(parse_date_or_date_time(&s))),
                 None => None,
             }.unwrap_or_else(||DateOrDateTime::Date(Local::today().naive_local()));
 
             let to = match to {
-                Some(s) => parse_date_or_date_time(&s),
+                Some(s) => or_exit(parse_date_or_date_time(&s)),
                 None => match from {
                     DateOrDateTime::DateTime(from) => DateOrDateTime::Date(from.date()),
                     from => from,
                 },
             };
-            (f, Some(from), Some(to))
+            Some((f, Some(from), Some(to)))
         }
-    };
-    let mut data_iterator = data
-        .iter()
-        .filter(|entry| iif!(filter.clone().unwrap_or_default() == "all", true, match from {
+    }
+}
+
+/// apply the resolved filter/date range from [`resolve_range`] to the tracked events
+fn filter_events(
+    data: &[TrackingEvent],
+    filter: Option<String>,
+    from: Option<DateOrDateTime>,
+    to: Option<DateOrDateTime>,
+) -> Vec<&TrackingEvent> {
+    let filter_from = filter.clone();
+    let filter_to = filter.clone();
+    data.iter()
+        .filter(|entry| iif!(filter_from.clone().unwrap_or_default() == "all", true, match from {
             None => true,
             Some(DateOrDateTime::Date(from)) => {
                 entry.time(true).timestamp_millis()
@@ -261,7 +720,7 @@ fn show(
                         .timestamp_millis()
             }
         }))
-        .filter(|entry| iif!(filter.clone().unwrap_or_default() == "all", true, match to {
+        .filter(|entry| iif!(filter_to.clone().unwrap_or_default() == "all", true, match to {
             None => true,
             Some(DateOrDateTime::Date(to)) => {
                 entry.time(true).timestamp_millis()
@@ -287,35 +746,133 @@ fn show(
                 (None, _) => true,
             },
         })
-        .skip_while(|entry| TrackingEvent::is_stop(entry));
+        .collect()
+}
+
+/// print the overtime/undertime balance against `schedule`'s expected hours for `[from, to]`
+fn print_balance(schedule: Option<&Schedule>, from: DateOrDateTime, to: DateOrDateTime, actual: Duration) {
+    if let Some(schedule) = schedule {
+        let expected = schedule.expand(from.date(), to.date());
+        let diff = actual.num_seconds() - expected.num_seconds();
+        let sign = if diff < 0 { "-" } else { "+" };
+        let (hours, minutes, seconds) = split_duration(Duration::seconds(diff.abs()));
+        println!("Balance: {}{:02}:{:02}:{:02}", sign, hours, minutes, seconds);
+    }
+}
+
+fn show(
+    data: &[TrackingEvent],
+    schedule: Option<&Schedule>,
+    from: Option<String>,
+    to: Option<String>,
+    filter: Option<String>,
+    include_seconds: bool,
+) -> Option<()> {
+    let (filter, from, to) = resolve_range(from, to, filter)?;
+    let filtered = filter_events(data, filter, from, to);
     let mut work_day = Duration::zero();
-    loop {
-        let start = data_iterator.next();
-        let stop = data_iterator.next();
-        match (start, stop) {
-            (Some(start), Some(stop)) => {
-                let duration = stop.time(include_seconds) - start.time(include_seconds);
-                work_day = work_day
-                    .checked_add(&duration)
-                    .expect("couldn't add up durations");
+    for interval in pair_events(filtered.into_iter()) {
+        let duration = match interval {
+            Interval::Closed(start, stop) => {
+                stop.time(include_seconds) - start.time(include_seconds)
             }
-            (Some(start), None) => {
+            Interval::Open(start) => {
                 let now = if include_seconds {
                     Utc::now()
                 } else {
                     Utc::now().with_second(0).unwrap()
                 };
-                let duration = now - start.time(include_seconds);
-                work_day = work_day
-                    .checked_add(&duration)
-                    .expect("couldn't add up durations");
-                break;
+                now - start.time(include_seconds)
             }
-            (_, _) => break,
-        }
+        };
+        work_day = work_day
+            .checked_add(&duration)
+            .expect("couldn't add up durations");
     }
     let (hours, minutes, seconds) = split_duration(work_day);
     println!("Work Time: {:02}:{:02}:{:02}", hours, minutes, seconds);
+    print_balance(schedule, from.unwrap(), to.unwrap(), work_day);
+    Some(())
+}
+
+fn report(
+    data: &[TrackingEvent],
+    schedule: Option<&Schedule>,
+    from: Option<String>,
+    to: Option<String>,
+    filter: Option<String>,
+    include_seconds: bool,
+) -> Option<()> {
+    let is_week = filter.as_deref() == Some("week");
+    let (filter, from, to) = resolve_range(from, to, filter)?;
+    let filtered = filter_events(data, filter, from, to);
+
+    let mut totals: HashMap<String, Duration> = HashMap::new();
+    let mut daily: HashMap<NaiveDate, HashMap<String, Duration>> = HashMap::new();
+
+    for interval in pair_events(filtered.into_iter()) {
+        let (start, stop_time) = match interval {
+            Interval::Closed(start, stop) => (start, stop.time(include_seconds)),
+            Interval::Open(start) => {
+                let now = if include_seconds {
+                    Utc::now()
+                } else {
+                    Utc::now().with_second(0).unwrap()
+                };
+                (start, now)
+            }
+        };
+        let duration = stop_time - start.time(include_seconds);
+        let key = start
+            .description()
+            .unwrap_or_else(|| "(no description)".to_owned());
+
+        let entry = totals.entry(key.clone()).or_insert_with(Duration::zero);
+        *entry = entry
+            .checked_add(&duration)
+            .expect("couldn't add up durations");
+
+        if is_week {
+            let date = start.time(include_seconds).with_timezone(&Local).date().naive_local();
+            let entry = daily
+                .entry(date)
+                .or_default()
+                .entry(key)
+                .or_insert_with(Duration::zero);
+            *entry = entry
+                .checked_add(&duration)
+                .expect("couldn't add up durations");
+        }
+    }
+
+    let mut totals: Vec<_> = totals.into_iter().collect();
+    totals.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+
+    let mut grand_total = Duration::zero();
+    for (description, duration) in &totals {
+        let (hours, minutes, seconds) = split_duration(*duration);
+        println!("{:02}:{:02}:{:02}  {}", hours, minutes, seconds, description);
+        grand_total = grand_total
+            .checked_add(duration)
+            .expect("couldn't add up durations");
+    }
+    let (hours, minutes, seconds) = split_duration(grand_total);
+    println!("Total: {:02}:{:02}:{:02}", hours, minutes, seconds);
+    print_balance(schedule, from.unwrap(), to.unwrap(), grand_total);
+
+    if is_week {
+        let mut dates: Vec<_> = daily.keys().copied().collect();
+        dates.sort();
+        for date in dates {
+            println!("{}:", date);
+            let mut day_totals: Vec<_> = daily[&date].iter().collect();
+            day_totals.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+            for (description, duration) in day_totals {
+                let (hours, minutes, seconds) = split_duration(*duration);
+                println!("  {:02}:{:02}:{:02}  {}", hours, minutes, seconds, description);
+            }
+        }
+    }
     Some(())
 }
 
@@ -347,6 +904,127 @@ fn status(data: &[TrackingEvent]) {
     }
 }
 
+#[cfg(feature = "binary")]
+/// escapes text for safe inclusion as both HTML element content and an
+/// attribute value, since calendar labels come from free-form user input
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(feature = "binary")]
+/// assign a stable, readable color to a description so recurring task types
+/// are visually distinguishable across the calendar
+fn description_color(description: &Option<String>) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    description.hash(&mut hasher);
+    let hue = hasher.finish() % 360;
+    format!("hsl({}, 65%, 55%)", hue)
+}
+
+#[cfg(feature = "binary")]
+fn render_calendar(data: &[TrackingEvent], from: NaiveDate, to: NaiveDate, private: bool) -> String {
+    let days = (to - from).num_days().max(0) + 1;
+
+    let mut columns = String::new();
+    for day in 0..days {
+        let date = from + Duration::days(day);
+
+        let mut blocks = String::new();
+        for interval in pair_events(data.iter()) {
+            let (start, stop, description) = match interval {
+                Interval::Closed(start, stop) => (
+                    start.time(true).with_timezone(&Local),
+                    stop.time(true).with_timezone(&Local),
+                    start.description(),
+                ),
+                Interval::Open(start) => (
+                    start.time(true).with_timezone(&Local),
+                    Utc::now().with_timezone(&Local),
+                    start.description(),
+                ),
+            };
+
+            if start.date().naive_local() != date {
+                continue;
+            }
+
+            let top = (start.hour() * 60 + start.minute()) as f64 / 1440.0 * 100.0;
+            let height = (stop.signed_duration_since(start).num_minutes() as f64 / 1440.0 * 100.0)
+                .max(0.5);
+            let label = if private {
+                "busy".to_owned()
+            } else {
+                description.clone().unwrap_or_else(|| "(no description)".to_owned())
+            };
+            let label = escape_html(&label);
+            let color = description_color(&description);
+
+            blocks.push_str(&format!(
+                r#"<div class="block" style="top:{:.3}%;height:{:.3}%;background:{};" title="{}">{}</div>"#,
+                top, height, color, label, label
+            ));
+        }
+
+        columns.push_str(&format!(
+            r#"<div class="day"><div class="day-header">{}</div><div class="day-body">{}</div></div>"#,
+            date.format("%a %Y-%m-%d"),
+            blocks
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Time Tracking Calendar</title>
+<style>
+body {{ font-family: sans-serif; margin: 0; padding: 1rem; }}
+.week {{ display: flex; }}
+.day {{ flex: 1; border-left: 1px solid #ccc; }}
+.day-header {{ text-align: center; font-weight: bold; padding: 0.25rem; }}
+.day-body {{ position: relative; height: 1440px; border-top: 1px solid #ccc; }}
+.block {{ position: absolute; left: 2px; right: 2px; overflow: hidden; border-radius: 3px; color: #fff; font-size: 0.7rem; padding: 1px 3px; }}
+</style>
+</head>
+<body>
+<div class="week">{}</div>
+</body>
+</html>
+"#,
+        columns
+    )
+}
+
+#[cfg(feature = "binary")]
+fn calendar(
+    data: &[TrackingEvent],
+    from: Option<String>,
+    to: Option<String>,
+    private: bool,
+    path: PathBuf,
+) {
+    let now = Local::today().naive_local();
+    let offset = i64::from(now.weekday().num_days_from_monday());
+    let default_from = now - Duration::days(offset);
+
+    let from = from
+        .map(|s| or_exit(parse_date_or_date_time(&s)).date())
+        .unwrap_or(default_from);
+    let to = to
+        .map(|s| or_exit(parse_date_or_date_time(&s)).date())
+        .unwrap_or(from + Duration::days(6));
+
+    let html = render_calendar(data, from, to, private);
+    std::fs::write(path, html).expect("could not write calendar file");
+}
+
 fn main() {
     let Options { command } = Options::from_args();
 
@@ -361,22 +1039,48 @@ fn main() {
     let mut data = read_data(&path);
 
     match command {
-        Command::Start { description, at } => start_tracking(&mut data, description, at),
-        Command::Stop { description, at } => stop_tracking(&mut data, description, at),
-        Command::Continue => continue_tracking(&mut data),
-        Command::List => data.iter().for_each(|e| println!("{:?}", e)),
+        Command::Start { description, at } => start_tracking(&mut data.events, description, at),
+        Command::Stop { description, at } => stop_tracking(&mut data.events, description, at),
+        Command::Continue => continue_tracking(&mut data.events),
+        Command::List => data.events.iter().for_each(|e| println!("{:?}", e)),
         Command::Path => println!("{}", path.to_string_lossy()),
         Command::Show {
             from,
             to,
             filter,
             include_seconds,
-        } => show(&data, from, to, filter, include_seconds).unwrap(),
-        Command::Status => status(&data),
+        } => show(&data.events, data.schedule.as_ref(), from, to, filter, include_seconds).unwrap(),
+        Command::Report {
+            from,
+            to,
+            filter,
+            include_seconds,
+        } => report(&data.events, data.schedule.as_ref(), from, to, filter, include_seconds).unwrap(),
+        Command::Status => status(&data.events),
         #[cfg(feature = "binary")]
         Command::Export { path } => {
             write_data_json(path, &data);
         }
+        #[cfg(feature = "binary")]
+        Command::Calendar {
+            from,
+            to,
+            private,
+            path,
+        } => calendar(&data.events, from, to, private, path),
+        #[cfg(feature = "binary")]
+        Command::ExportCsv { path } => export_csv(path, &data.events),
+        #[cfg(feature = "binary")]
+        Command::ImportCsv { path } => data.events = import_csv(path),
+        #[cfg(feature = "binary")]
+        Command::ExportOrg { path } => export_org(path, &data.events),
+        #[cfg(feature = "binary")]
+        Command::ImportOrg { path } => data.events = import_org(path),
+        Command::Schedule {
+            rrule,
+            daily_target,
+            from,
+        } => set_schedule(&mut data, rrule, daily_target, from),
         #[allow(unreachable_patterns)]
         _ => unimplemented!(),
     }
@@ -384,75 +1088,140 @@ fn main() {
     write_data(path, &data);
 }
 
-fn parse_date_time(s: &str) -> DateTime<Utc> {
-    if let Ok(time) = NaiveTime::parse_from_str(s, "%H:%M:%S") {
-        let today = Local::today();
-        let date_time = today.and_time(time).unwrap();
-        return date_time.with_timezone(&Utc);
+/// print a parse error and exit, for use as the error arm of `unwrap_or_else` on user-supplied
+/// timestamps, so malformed `--at`/`--from`/`--to` values no longer panic
+fn exit_with_error(err: String) -> ! {
+    eprintln!("error: {}", err);
+    std::process::exit(1);
+}
+
+/// unwraps a parse result or exits via [`exit_with_error`]
+fn or_exit<T>(result: Result<T, String>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => exit_with_error(err),
     }
-    if let Ok(time) = NaiveTime::parse_from_str(&format!("{}:0", s), "%H:%M:%S") {
-        let today = Local::today();
-        let date_time = today.and_time(time).unwrap();
-        return date_time.with_timezone(&Utc);
+}
+
+fn parse_date_time(s: &str) -> Result<DateTime<Utc>, String> {
+    let naive = match parse_date_or_date_time(s)? {
+        DateOrDateTime::DateTime(naive) => naive,
+        DateOrDateTime::Date(date) => date.and_time(NaiveTime::from_hms(0, 0, 0)),
+    };
+    Ok(TimeZone::from_local_datetime(&Local, &naive)
+        .unwrap()
+        .with_timezone(&Utc))
+}
+
+/// parse a `%H:%M:%S` time, also accepting the shorter `%H:%M` and bare `%H` forms
+fn parse_flexible_naive_time(s: &str) -> chrono::ParseResult<NaiveTime> {
+    NaiveTime::parse_from_str(s, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(&format!("{}:0", s), "%H:%M:%S"))
+        .or_else(|_| NaiveTime::parse_from_str(&format!("{}:0:0", s), "%H:%M:%S"))
+}
+
+/// parse a `%Y-%m-%d %H:%M:%S` date-time, also accepting the shorter `%Y-%m-%d %H:%M` form
+fn parse_flexible_naive_date_time(s: &str) -> chrono::ParseResult<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(&format!("{}:0", s), "%Y-%m-%d %H:%M:%S"))
+}
+
+/// parse a signed relative offset like `-15m`, `+2h` or `15m ago` into a point in time,
+/// relative to [`Local::now`]. Returns `Ok(None)` when `s` isn't a relative expression at all
+fn parse_relative(s: &str) -> Result<Option<NaiveDateTime>, String> {
+    let (explicit_ago, body) = match s.strip_suffix("ago") {
+        Some(rest) => (true, rest.trim()),
+        None => (false, s),
+    };
+
+    let (sign, digits_and_unit) = if let Some(rest) = body.strip_prefix('-') {
+        (Some(-1i64), rest)
+    } else if let Some(rest) = body.strip_prefix('+') {
+        (Some(1i64), rest)
+    } else {
+        (None, body)
+    };
+
+    if sign.is_none() && !explicit_ago {
+        return Ok(None);
     }
-    if let Ok(time) = NaiveTime::parse_from_str(&format!("{}:0:0", s), "%H:%M:%S") {
-        let today = Local::today();
-        let date_time = today.and_time(time).unwrap();
-        return date_time.with_timezone(&Utc);
+    if digits_and_unit.is_empty() {
+        return Err(format!("invalid relative offset `{}`", s));
     }
-    if let Ok(date_time) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
-        return TimeZone::from_local_datetime(&Local, &date_time)
-            .unwrap()
-            .with_timezone(&Utc);
+
+    let unit = digits_and_unit
+        .chars()
+        .last()
+        .ok_or_else(|| format!("invalid relative offset `{}`", s))?;
+    let amount: i64 = digits_and_unit[..digits_and_unit.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| format!("invalid relative offset `{}`", s))?;
+
+    let magnitude = match unit {
+        's' => Duration::seconds(amount),
+        'm' => Duration::minutes(amount),
+        'h' => Duration::hours(amount),
+        'd' => Duration::days(amount),
+        other => return Err(format!("unknown relative offset unit `{}`", other)),
+    };
+    let offset = if sign == Some(1) { magnitude } else { -magnitude };
+
+    Ok(Some((Local::now() + offset).naive_local()))
+}
+
+/// parse `today`/`yesterday`, optionally followed by a time of day, e.g. `yesterday 09:00`.
+/// Returns `Ok(None)` when `s` doesn't start with either keyword
+fn parse_day_anchor(s: &str) -> Result<Option<NaiveDateTime>, String> {
+    let mut parts = s.splitn(2, char::is_whitespace);
+    let anchor = parts.next().unwrap_or_default();
+    let time_part = parts.next().map(str::trim).filter(|s| !s.is_empty());
+
+    let date = match anchor {
+        "today" => Local::today().naive_local(),
+        "yesterday" => Local::today().naive_local() - Duration::days(1),
+        _ => return Ok(None),
+    };
+
+    let time = match time_part {
+        Some(time_str) => parse_flexible_naive_time(time_str)
+            .map_err(|_| format!("invalid time `{}` in `{}`", time_str, s))?,
+        None => NaiveTime::from_hms(0, 0, 0),
+    };
+
+    Ok(Some(date.and_time(time)))
+}
+
+/// parse a date/date-time the tracker understands: full RFC3339 (`T` or space separator,
+/// explicit offset), the `YY-mm-dd HH:MM:SS`/`HH:MM:SS` forms, a relative offset (`-15m`,
+/// `2h ago`) or a `today`/`yesterday` anchor
+fn parse_date_or_date_time(s: &str) -> Result<DateOrDateTime, String> {
+    let s = s.trim();
+
+    if let Some(date_time) = parse_relative(s)? {
+        return Ok(DateOrDateTime::DateTime(date_time));
     }
-    if let Ok(date_time) = NaiveDateTime::parse_from_str(&format!("{}:0", s), "%Y-%m-%d %H:%M:%S") {
-        return TimeZone::from_local_datetime(&Local, &date_time)
-            .unwrap()
-            .with_timezone(&Utc);
+    if let Some(date_time) = parse_day_anchor(s)? {
+        return Ok(DateOrDateTime::DateTime(date_time));
     }
-    let date_time =
-        NaiveDateTime::parse_from_str(&format!("{}:0:0", s), "%Y-%m-%d %H:%M:%S").unwrap();
-    TimeZone::from_local_datetime(&Local, &date_time)
-        .unwrap()
-        .with_timezone(&Utc)
-}
-
-fn parse_date_or_date_time(s: &str) -> DateOrDateTime {
-    if let Ok(date) = NaiveDate::parse_from_str(&s, "%Y-%m-%d") {
-        return DateOrDateTime::Date(date);
-    }
-    if let Ok(date) =
-        NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S").map(DateOrDateTime::DateTime)
-    {
-        return date;
-    }
-    if let Ok(date) = NaiveTime::parse_from_str(&s, "%H:%M:%S")
-        .map(|time| Local::today().and_time(time).unwrap())
-        .map(|date_time| date_time.naive_local())
-        .map(DateOrDateTime::DateTime)
-    {
-        return date;
-    }
-    if let Ok(date) = NaiveTime::parse_from_str(&format!("{}:0", s), "%H:%M:%S")
-        .map(|time| Local::today().and_time(time).unwrap())
-        .map(|date_time| date_time.naive_local())
-        .map(DateOrDateTime::DateTime)
-    {
-        return date;
-    }
-    if let Ok(date) = NaiveTime::parse_from_str(&format!("{}:0:0", s), "%H:%M:%S")
-        .map(|time| Local::today().and_time(time).unwrap())
-        .map(|date_time| date_time.naive_local())
-        .map(DateOrDateTime::DateTime)
-    {
-        return date;
-    }
-    if let Ok(date) = NaiveDateTime::parse_from_str(&format!("{}:0", s), "%Y-%m-%d %H:%M:%S")
-        .map(DateOrDateTime::DateTime)
-    {
-        return date;
-    }
-    NaiveDateTime::parse_from_str(&format!("{}:0:0", s), "%Y-%m-%d %H:%M:%S")
-        .map(DateOrDateTime::DateTime)
-        .unwrap()
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(&s.replacen(' ', "T", 1)) {
+        return Ok(DateOrDateTime::DateTime(
+            date_time.with_timezone(&Local).naive_local(),
+        ));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(DateOrDateTime::Date(date));
+    }
+    if let Ok(date_time) = parse_flexible_naive_date_time(s) {
+        return Ok(DateOrDateTime::DateTime(date_time));
+    }
+    if let Ok(time) = parse_flexible_naive_time(s) {
+        return Ok(DateOrDateTime::DateTime(
+            Local::today().naive_local().and_time(time),
+        ));
+    }
+
+    Err(format!(
+        "could not parse `{}` as a date, time or relative offset",
+        s
+    ))
 }